@@ -0,0 +1,103 @@
+//! `#[derive(ParquetComponent)]`: generates a `bevy_parquet::ParquetRecordWriter` impl that
+//! writes a component's fields to explicit, declaration-ordered Parquet columns instead of
+//! relying on `ParquetConfig::component_clusters`' opaque `(String, ComponentId)` pairs.
+//!
+//! Mirrors `parquet_derive`'s `ParquetRecordWriter`: annotate a field with
+//! `#[parquet(rename = "...")]` to change its column name, or `#[parquet(skip)]` to omit it
+//! entirely. `Option<T>` fields are marked nullable; everything else isn't.
+
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Fields, Type};
+
+#[proc_macro_derive(ParquetComponent, attributes(parquet))]
+pub fn derive_parquet_component(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let struct_name = &input.ident;
+
+    let Data::Struct(data) = &input.data else {
+        return syn::Error::new_spanned(&input, "ParquetComponent can only be derived for structs")
+            .to_compile_error()
+            .into();
+    };
+    let Fields::Named(fields) = &data.fields else {
+        return syn::Error::new_spanned(
+            &input,
+            "ParquetComponent requires named fields (no tuple structs or unit structs)",
+        )
+        .to_compile_error()
+        .into();
+    };
+
+    let mut descriptors = Vec::new();
+    let mut value_pushes = Vec::new();
+
+    for field in &fields.named {
+        let field_ident = field.ident.as_ref().expect("named field");
+
+        let mut column_name = field_ident.to_string();
+        let mut skip = false;
+        for attr in &field.attrs {
+            if !attr.path().is_ident("parquet") {
+                continue;
+            }
+            let parse_result = attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("skip") {
+                    skip = true;
+                    Ok(())
+                } else if meta.path.is_ident("rename") {
+                    let value = meta.value()?;
+                    let literal: syn::LitStr = value.parse()?;
+                    column_name = literal.value();
+                    Ok(())
+                } else {
+                    Err(meta.error("unsupported #[parquet(..)] attribute, expected `rename = \"...\"` or `skip`"))
+                }
+            });
+            if let Err(e) = parse_result {
+                return e.to_compile_error().into();
+            }
+        }
+
+        if skip {
+            continue;
+        }
+
+        let nullable = is_option(&field.ty);
+        descriptors.push(quote! {
+            ::bevy_parquet::ParquetFieldDescriptor {
+                name: #column_name.to_string(),
+                nullable: #nullable,
+            }
+        });
+        value_pushes.push(quote! {
+            ::bevy::reflect::Reflect::clone_value(&self.#field_ident)
+        });
+    }
+
+    let expanded = quote! {
+        impl ::bevy_parquet::ParquetRecordWriter for #struct_name {
+            fn parquet_fields() -> ::std::vec::Vec<::bevy_parquet::ParquetFieldDescriptor> {
+                ::std::vec![#(#descriptors),*]
+            }
+
+            fn parquet_values(&self) -> ::std::vec::Vec<::std::boxed::Box<dyn ::bevy::reflect::Reflect>> {
+                ::std::vec![#(#value_pushes),*]
+            }
+        }
+    };
+
+    expanded.into()
+}
+
+/// Whether `ty` is (textually) an `Option<...>`, used to mark a column nullable.
+fn is_option(ty: &Type) -> bool {
+    let Type::Path(type_path) = ty else {
+        return false;
+    };
+    type_path
+        .path
+        .segments
+        .last()
+        .is_some_and(|segment| segment.ident == "Option")
+}
@@ -0,0 +1,114 @@
+use std::collections::HashMap;
+
+use parquet::basic::{Compression, Encoding, GzipLevel, ZstdLevel};
+use parquet::file::properties::WriterProperties;
+use parquet::schema::types::ColumnPath;
+
+/// Compression codec choice exposed on `ParquetConfig`, independent of the `parquet` crate's own
+/// `Compression` enum so overrides stay `Copy`/`PartialEq` (levelled codecs in `parquet::basic`
+/// carry a level struct, which isn't).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ColumnCodec {
+    /// No compression. Cheapest to write and read, largest on disk.
+    Uncompressed,
+    /// Fast, low-CPU compression; the right default for high-frequency frame captures.
+    Snappy,
+    /// Slower but denser than Snappy; a good fit for archival exports.
+    Zstd,
+    Lz4,
+    Gzip,
+}
+
+impl From<ColumnCodec> for Compression {
+    fn from(codec: ColumnCodec) -> Self {
+        match codec {
+            ColumnCodec::Uncompressed => Compression::UNCOMPRESSED,
+            ColumnCodec::Snappy => Compression::SNAPPY,
+            ColumnCodec::Zstd => Compression::ZSTD(ZstdLevel::default()),
+            ColumnCodec::Lz4 => Compression::LZ4,
+            ColumnCodec::Gzip => Compression::GZIP(GzipLevel::default()),
+        }
+    }
+}
+
+/// Column encoding choice exposed on `ParquetConfig`. Covers the encodings that make sense to
+/// pick by hand for a given column's value distribution; anything more exotic should be set
+/// directly on a caller-supplied `WriterProperties` via `parquet`'s own API.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ColumnEncoding {
+    Plain,
+    Dictionary,
+    DeltaBinaryPacked,
+    ByteStreamSplit,
+}
+
+impl From<ColumnEncoding> for Encoding {
+    fn from(encoding: ColumnEncoding) -> Self {
+        match encoding {
+            ColumnEncoding::Plain => Encoding::PLAIN,
+            ColumnEncoding::Dictionary => Encoding::RLE_DICTIONARY,
+            ColumnEncoding::DeltaBinaryPacked => Encoding::DELTA_BINARY_PACKED,
+            ColumnEncoding::ByteStreamSplit => Encoding::BYTE_STREAM_SPLIT,
+        }
+    }
+}
+
+/// Replaces a single opaque `WriterProperties` with compression/encoding overrides applied on top
+/// of a default, picked per cluster (keyed the same way `recorder::cluster_signature` keys an
+/// open `ParquetWriter`) and per column within it. A telemetry cluster of floats and a
+/// string-heavy metadata cluster usually want very different settings; this is the knob for that,
+/// without every caller having to hand-build a full `WriterProperties`.
+#[derive(Clone, Debug)]
+pub struct CompressionConfig {
+    /// Applied to every column that no cluster or column override matches.
+    pub default_codec: ColumnCodec,
+    /// Overrides `default_codec` for every column of the cluster with this signature.
+    pub cluster_codec: HashMap<String, ColumnCodec>,
+    /// Overrides `default_codec`/`cluster_codec` for this column name specifically, regardless of
+    /// which cluster it's written in.
+    pub column_codec: HashMap<String, ColumnCodec>,
+    /// Per-column encoding overrides. Unset columns use `parquet`'s own default encoding
+    /// selection.
+    pub column_encoding: HashMap<String, ColumnEncoding>,
+}
+
+impl Default for CompressionConfig {
+    fn default() -> Self {
+        Self {
+            default_codec: ColumnCodec::Snappy,
+            cluster_codec: HashMap::new(),
+            column_codec: HashMap::new(),
+            column_encoding: HashMap::new(),
+        }
+    }
+}
+
+/// Builds the `WriterProperties` for one file: `default_codec`, overridden by `cluster_codec` for
+/// `cluster_signature` if present, then overridden again per column by `column_codec`/
+/// `column_encoding` for any name in `column_names`.
+pub(crate) fn build_writer_properties(
+    config: &CompressionConfig,
+    cluster_signature: &str,
+    column_names: &[String],
+) -> WriterProperties {
+    let cluster_codec = config
+        .cluster_codec
+        .get(cluster_signature)
+        .copied()
+        .unwrap_or(config.default_codec);
+
+    let mut builder = WriterProperties::builder().set_compression(cluster_codec.into());
+
+    for column_name in column_names {
+        let path = ColumnPath::from(column_name.as_str());
+
+        if let Some(codec) = config.column_codec.get(column_name) {
+            builder = builder.set_column_compression(path.clone(), (*codec).into());
+        }
+        if let Some(encoding) = config.column_encoding.get(column_name) {
+            builder = builder.set_column_encoding(path, (*encoding).into());
+        }
+    }
+
+    builder.build()
+}
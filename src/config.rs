@@ -1,6 +1,8 @@
 use bevy::prelude::*;
 use bevy::ecs::component::ComponentId;
-use parquet::file::properties::WriterProperties;
+
+use crate::compression::CompressionConfig;
+use crate::record_writer::ParquetRecordWriterRegistry;
 
 /// Configuration for the ParquetPlugin
 #[derive(Clone, Resource)]
@@ -10,8 +12,78 @@ pub struct ParquetConfig {
     pub file_name: Option<String>,
     /// Optional manual component clusters
     pub component_clusters: Option<Vec<Vec<(String, ComponentId)>>>,
-    /// Parquet writer properties
-    pub writer_properties: WriterProperties,
+    /// Compression codec and column encoding, with per-cluster/per-column overrides. The
+    /// `WriterProperties` passed to each file's `ArrowWriter` is built from this at write time by
+    /// `compression::build_writer_properties`.
+    pub compression: CompressionConfig,
+    /// Also emit a `{output_path}_resources.parquet` table with one row containing every
+    /// reflected resource (any type registered with `ReflectResource`). Off by default so
+    /// existing callers' output doesn't change shape.
+    pub serialize_resources: bool,
+    /// Enables the `ParquetRecorder` subsystem: a continuous recorder that appends a snapshot to
+    /// each cluster's open writer whenever a tracked component changes, instead of requiring a manual
+    /// `serialize_world` call. `None` disables it entirely.
+    pub recorder: Option<RecorderConfig>,
+    /// Jaccard similarity threshold (over component-id sets, 0.0-1.0) above which two
+    /// entities' exact component-set groups are merged into one cluster during automatic
+    /// detection. Merged clusters take the union of columns, so raising this only controls how
+    /// eagerly dissimilar entity shapes get lumped into the same file; it never drops a column.
+    pub cluster_merge_threshold: f32,
+    /// How each cluster's Arrow schema (and the row values that must match it) is derived.
+    /// Defaults to `Reflected`, the existing reflection-based path.
+    pub schema_inference: SchemaSource,
+    /// Extra `(key, value)` pairs stamped into every written file's Parquet footer, alongside a
+    /// few pairs this plugin always adds (crate version, export timestamp) — see
+    /// `metadata::footer_metadata`. Lets downstream readers validate a file's provenance (app
+    /// version, world tick at export, schema version, ...) without a side-channel.
+    pub key_value_metadata: Option<Vec<(String, String)>>,
+    /// Components registered via `#[derive(ParquetComponent)]` whose explicit column layout
+    /// should be used in place of `create_arrow_schema`'s reflection-based inference. Empty by
+    /// default, so existing callers' schemas are unaffected until they call
+    /// `ParquetRecordWriterRegistry::register`.
+    pub record_writers: ParquetRecordWriterRegistry,
+}
+
+/// Chooses how `serialize_world` derives a cluster's Arrow schema.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SchemaSource {
+    /// Reflect one representative instance per component (`create_arrow_schema`) and map its
+    /// `Reflect` value to an Arrow type by hand. Works for any `Reflect` component, but the type
+    /// mapping is whatever `arrow_type_for_reflect` happens to know about.
+    #[default]
+    Reflected,
+    /// Serialize every entity's components to `serde_json::Value` via `ReflectSerialize` and
+    /// trace the Arrow schema from those sample rows with `serde_arrow`. Only usable for
+    /// components that register `ReflectSerialize`, but picks up richer types (dates, nested
+    /// enums) that the reflection-based mapping falls back to `Utf8` for.
+    Traced,
+}
+
+/// Tunables for the `ParquetRecorder` subsystem.
+#[derive(Clone, Default)]
+pub struct RecorderConfig {
+    /// Force a snapshot at least every N frames even if nothing changed. `None` means rely
+    /// solely on change detection, so fully idle frames never write anything.
+    pub interval_frames: Option<u32>,
+    /// When a cluster's open writer forces its buffered rows into a new row group, instead of
+    /// relying solely on `parquet`'s own buffered-row-count threshold. `None` keeps the prior
+    /// behaviour of leaving that entirely up to `parquet`, so row groups (and therefore memory
+    /// use) are bounded only by that row count, however many bytes the rows hold.
+    pub flush_policy: Option<FlushPolicy>,
+}
+
+/// A cluster's open `ParquetWriter` is kept across frames by the `ParquetRecorder` subsystem;
+/// this picks when it forces a row-group boundary instead of waiting for `parquet`'s own
+/// buffered-row threshold, so long captures get both bounded memory and well-sized row groups for
+/// downstream predicate pushdown.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FlushPolicy {
+    /// Flush at least every N recorded snapshots for a cluster, even if few bytes have been
+    /// buffered.
+    EveryFrames(u32),
+    /// Flush once roughly this many bytes (`RecordBatch::get_array_memory_size`, summed across
+    /// snapshots since the last flush) have been buffered for a cluster.
+    RowGroupBytes(usize),
 }
 
 impl Default for ParquetConfig {
@@ -20,7 +92,13 @@ impl Default for ParquetConfig {
             output_path: "./".to_string(),
             file_name: None,
             component_clusters: None,
-            writer_properties: WriterProperties::builder().build(),
+            compression: CompressionConfig::default(),
+            serialize_resources: false,
+            recorder: None,
+            cluster_merge_threshold: 0.8,
+            schema_inference: SchemaSource::default(),
+            key_value_metadata: None,
+            record_writers: ParquetRecordWriterRegistry::default(),
         }
     }
 }
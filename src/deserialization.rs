@@ -0,0 +1,220 @@
+use arrow::array::{Array, ArrayRef, BooleanArray, Float32Array, Float64Array, Int32Array, Int64Array, RecordBatch, StringArray, StructArray};
+use arrow::datatypes::DataType;
+use bevy::prelude::*;
+use bevy::reflect::{DynamicList, DynamicStruct, Reflect, ReflectComponent, TypeRegistry};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use crate::ParquetError;
+
+/// Finds every cluster file written by a previous `serialize_world(..)` call for `path`, i.e.
+/// every `{path}_*.parquet` sibling file, sorted for deterministic load order.
+pub(crate) fn cluster_files(path: &str) -> Result<Vec<PathBuf>, ParquetError> {
+    let path = Path::new(path);
+    let dir = path
+        .parent()
+        .filter(|p| !p.as_os_str().is_empty())
+        .unwrap_or_else(|| Path::new("."));
+    let prefix = format!(
+        "{}_",
+        path.file_name().map(|f| f.to_string_lossy()).unwrap_or_default()
+    );
+
+    let mut files = Vec::new();
+    for entry in std::fs::read_dir(dir).map_err(ParquetError::Io)? {
+        let entry = entry.map_err(ParquetError::Io)?;
+        let file_name = entry.file_name().to_string_lossy().to_string();
+        if file_name.starts_with(&prefix) && file_name.ends_with(".parquet") {
+            files.push(entry.path());
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Whether `registration`'s represented type is actually `String`. `Utf8` is both the real Arrow
+/// type for a `String` component and the write-side fallback `arrow_type_for_reflect` uses for
+/// anything it can't classify (tuple structs, enums, maps, ...), so a `Utf8` column can't be
+/// assumed to hold a `String` without checking this first.
+fn represents_string(registration: &bevy::reflect::TypeRegistration) -> bool {
+    registration.type_info().type_path() == "alloc::string::String"
+}
+
+/// Looks up the `TypeRegistration` whose short name (the part of its type path after the last
+/// `::`) matches `column_name`, mirroring the name-shortening `create_arrow_schema` applies when
+/// naming columns.
+pub(crate) fn find_registration_by_short_name<'a>(
+    type_registry: &'a TypeRegistry,
+    column_name: &str,
+) -> Option<&'a bevy::reflect::TypeRegistration> {
+    type_registry
+        .iter()
+        .find(|reg| reg.type_info().type_path().split("::").last() == Some(column_name))
+}
+
+/// Reconstructs a `Box<dyn Reflect>` for a single cell, inverting the type mapping
+/// `arrow_type_for_reflect` performs on write. Returns `None` for a null cell or for a `DataType`
+/// we don't know how to invert (e.g. `Int8`, `UInt32`). A `Utf8` cell always comes back as a
+/// `String`, even when the column is the write-side fallback for a type we couldn't classify.
+pub(crate) fn reflect_value_from_array(array: &ArrayRef, row: usize) -> Option<Box<dyn Reflect>> {
+    if array.is_null(row) {
+        return None;
+    }
+
+    match array.data_type() {
+        DataType::Float32 => {
+            let value = array.as_any().downcast_ref::<Float32Array>()?.value(row);
+            Some(Box::new(value))
+        }
+        DataType::Float64 => {
+            let value = array.as_any().downcast_ref::<Float64Array>()?.value(row);
+            Some(Box::new(value))
+        }
+        DataType::Int32 => {
+            let value = array.as_any().downcast_ref::<Int32Array>()?.value(row);
+            Some(Box::new(value))
+        }
+        DataType::Int64 => {
+            let value = array.as_any().downcast_ref::<Int64Array>()?.value(row);
+            Some(Box::new(value))
+        }
+        DataType::Boolean => {
+            let value = array.as_any().downcast_ref::<BooleanArray>()?.value(row);
+            Some(Box::new(value))
+        }
+        DataType::Utf8 => {
+            let value = array.as_any().downcast_ref::<StringArray>()?.value(row);
+            Some(Box::new(value.to_string()))
+        }
+        DataType::Struct(fields) => {
+            let struct_array = array.as_any().downcast_ref::<StructArray>()?;
+            let mut dynamic = DynamicStruct::default();
+            for (index, field) in fields.iter().enumerate() {
+                let child = struct_array.column(index);
+                if let Some(value) = reflect_value_from_array(child, row) {
+                    dynamic.insert_boxed(field.name(), value);
+                }
+            }
+            Some(Box::new(dynamic))
+        }
+        DataType::List(_) => {
+            let list_array = array.as_any().downcast_ref::<arrow::array::ListArray>()?;
+            let values = list_array.value(row);
+            let mut dynamic = DynamicList::default();
+            for item in 0..values.len() {
+                if let Some(value) = reflect_value_from_array(&values, item) {
+                    dynamic.push_box(value);
+                }
+            }
+            Some(Box::new(dynamic))
+        }
+        // No clean inverse for anything else (tuples, enums, maps serialized as text).
+        _ => None,
+    }
+}
+
+/// Parses the bit-packed `entity_id` column value (see `create_uuid_array`) back into the raw
+/// `u64` produced by `Entity::to_bits`.
+pub(crate) fn parse_entity_id(array: &ArrayRef, row: usize) -> Option<u64> {
+    array
+        .as_any()
+        .downcast_ref::<StringArray>()?
+        .value(row)
+        .parse()
+        .ok()
+}
+
+/// Maps a stable `entity_id` bit pattern onto the `Entity` spawned for it in this load, spawning
+/// a new entity the first time an id is seen so the same logical entity is reused across cluster
+/// files.
+pub(crate) fn entity_for_id(
+    world: &mut World,
+    entity_ids: &mut HashMap<u64, Entity>,
+    id: u64,
+) -> Entity {
+    *entity_ids
+        .entry(id)
+        .or_insert_with(|| world.spawn_empty().id())
+}
+
+/// Applies every row of one already-read `RecordBatch` to `world`, merging onto the `Entity`
+/// tracked in `entity_ids` for each row's `entity_id` column. Shared by `deserialize_world` (which
+/// reads every batch of every cluster file up front) and the `ParquetLoadPlugin` streaming system
+/// (which reads one batch at a time, possibly across several frames), so the two stay in lockstep
+/// as the on-disk format evolves.
+pub(crate) fn apply_batch(
+    world: &mut World,
+    entity_ids: &mut HashMap<u64, Entity>,
+    type_registry: &TypeRegistry,
+    batch: &RecordBatch,
+    file_path: &Path,
+) -> Result<(), ParquetError> {
+    let schema = batch.schema();
+
+    let Some(entity_id_column) = batch.column_by_name("entity_id") else {
+        tracing::warn!(file = ?file_path, "Cluster file has no entity_id column, skipping");
+        return Ok(());
+    };
+
+    for row in 0..batch.num_rows() {
+        let Some(id) = parse_entity_id(entity_id_column, row) else {
+            continue;
+        };
+        let entity = entity_for_id(world, entity_ids, id);
+
+        // Every cluster file kept by `serialize_world` originally carried the PhantomPersistTag
+        // marker (components without it are filtered out before writing), so re-tag the entity
+        // even though the column itself was stripped.
+        if let Some(tag_registration) = find_registration_by_short_name(type_registry, "PhantomPersistTag")
+        {
+            if let Some(reflect_tag) = tag_registration.data::<ReflectComponent>() {
+                let tag = DynamicStruct::default();
+                let mut entity_mut = world.entity_mut(entity);
+                reflect_tag.apply_or_insert(&mut entity_mut, &tag, type_registry);
+            }
+        }
+
+        for field in schema.fields() {
+            if field.name() == "entity_id" {
+                continue;
+            }
+
+            let Some(registration) = find_registration_by_short_name(type_registry, field.name())
+            else {
+                tracing::warn!(
+                    component = field.name(),
+                    "No type registration found for column, skipping"
+                );
+                continue;
+            };
+
+            let Some(reflect_component) = registration.data::<ReflectComponent>() else {
+                continue;
+            };
+
+            let column = batch.column_by_name(field.name()).unwrap();
+
+            // Utf8 is also write's fallback type for anything arrow_type_for_reflect can't
+            // classify (tuple structs, enums, maps, ...), so a Utf8 column only really holds a
+            // String if the component itself is one. Applying the reconstructed String to a
+            // component of some other represented type would panic inside apply_or_insert, so
+            // skip it instead, same as the other "can't invert this column" cases below.
+            if column.data_type() == &DataType::Utf8 && !represents_string(registration) {
+                tracing::warn!(
+                    component = field.name(),
+                    "Utf8 column is a write-side fallback for this component's represented type, skipping"
+                );
+                continue;
+            }
+
+            let Some(value) = reflect_value_from_array(column, row) else {
+                continue;
+            };
+
+            let mut entity_mut = world.entity_mut(entity);
+            reflect_component.apply_or_insert(&mut entity_mut, value.as_ref(), type_registry);
+        }
+    }
+
+    Ok(())
+}
@@ -1,25 +1,50 @@
 #![feature(trait_upcasting)]
 
+mod compression;
 mod config;
+mod load;
 mod persistence_tracking;
+mod record_writer;
+mod recorder;
 mod state;
 mod writer;
 
 use {
     arrow::{
-        array::{ArrayRef, Float32Builder, Int32Builder, RecordBatch, StringArray, StructArray},
-        datatypes::{DataType, Field},
+        array::{
+            ArrayRef, BooleanBuilder, Float32Builder, Float64Builder, Int16Builder, Int32Builder,
+            Int64Builder, Int8Builder, ListArray, RecordBatch, StringArray, StructArray,
+            UInt16Builder, UInt32Builder, UInt64Builder, UInt8Builder,
+        },
+        buffer::{NullBuffer, OffsetBuffer},
+        datatypes::DataType,
     },
     bevy::{
-        ecs::component::ComponentId,
+        ecs::{component::ComponentId, reflect::ReflectResource},
         prelude::*,
-        reflect::{ReflectRef, TypeRegistry},
+        reflect::{Reflect, ReflectRef, TypeRegistry},
     },
-    parquet::{arrow::ArrowWriter, basic::Compression, file::properties::WriterProperties},
+    parquet::arrow::ArrowWriter,
+    record_writer::ErasedParquetRecordWriter,
     std::{fmt::Debug, sync::Arc},
     thiserror::Error,
 };
-pub use {config::ParquetConfig, state::ParquetState};
+pub use {
+    compression::{ColumnCodec, ColumnEncoding, CompressionConfig},
+    config::{FlushPolicy, ParquetConfig, RecorderConfig, SchemaSource},
+    load::{load_world_async, ParquetLoadConfig, ParquetLoadPlugin},
+    record_writer::{
+        record_batch_for, ParquetFieldDescriptor, ParquetRecordWriter, ParquetRecordWriterRegistry,
+    },
+    state::ParquetState,
+};
+
+// Reachable as `bevy_parquet::ParquetComponent` so `#[derive(bevy_parquet::ParquetComponent)]`
+// works without a direct `bevy_parquet_derive` dependency.
+pub use bevy_parquet_derive::ParquetComponent;
+
+// The derive expands to `::bevy_parquet::...` paths; this makes them resolve inside this crate too.
+extern crate self as bevy_parquet;
 
 #[derive(Error, Debug)]
 pub enum ParquetError {
@@ -36,13 +61,24 @@ pub struct ParquetPlugin;
 impl Plugin for ParquetPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<ParquetConfig>()
-            .init_resource::<ParquetState>();
+            .init_resource::<ParquetState>()
+            // `ParquetRecorder`: appends a snapshot batch to each cluster's open writer on change
+            // detection / interval, keyed off `ParquetConfig::recorder`. No-ops when that field is
+            // unset.
+            .add_systems(Update, recorder::record_tick)
+            .add_systems(Last, recorder::finalize_on_exit);
     }
 }
 
 mod serialization;
 use serialization::*;
 
+mod deserialization;
+
+mod metadata;
+
+mod schema_trace;
+
 /// Trigger manual serialization of the world state to parquet
 pub fn serialize_world(world: &mut World) -> Result<(), ParquetError> {
     let binding = &world.resource::<AppTypeRegistry>().clone();
@@ -53,7 +89,7 @@ pub fn serialize_world(world: &mut World) -> Result<(), ParquetError> {
     let clusters = if let Some(ref manual_clusters) = config.component_clusters {
         manual_clusters.clone()
     } else {
-        detect_component_clusters(world)
+        detect_component_clusters(world, config.cluster_merge_threshold)
     };
     println!("Detected Clusters: {:?}", clusters);
     let mut state = world.resource_mut::<ParquetState>();
@@ -90,16 +126,122 @@ pub fn serialize_world(world: &mut World) -> Result<(), ParquetError> {
         );
     }
 
+    // Everything from here on only needs read access; re-borrowing as shared lets the parallel
+    // fan-out below hand the same `&World` to every spawned task.
+    let world: &World = world;
+
+    // Build the read-only per-cluster work items (entity list + component ids) up front so the
+    // array construction below can fan out across clusters; everything here only ever takes a
+    // shared `&World` borrow.
+    let cluster_entities: Vec<Vec<Entity>> = clusters
+        .iter()
+        .map(|cluster| {
+            world
+                .iter_entities()
+                .filter(|entity| {
+                    cluster
+                        .iter()
+                        .all(|component_pair| {
+                            world.get_by_id(entity.id(), component_pair.1).is_some()
+                        })
+                })
+                .map(|entity| entity.id())
+                .collect()
+        })
+        .collect();
+
+    // Fan out across the Bevy task pool: each cluster's `Vec<(String, ArrayRef)>` is built
+    // independently from an immutable world borrow, so all clusters can be processed in parallel
+    // before any `&mut World` access (the writes below) resumes.
+    //
+    // `&World` isn't `Send` (it may hold non-`Send` resources), so it can't be captured directly
+    // into `scope.spawn`'s futures. `UnsafeWorldCell` is `Send`, and reconstructing a `&World`
+    // from it inside each task is sound here because every task only ever reads, and nothing else
+    // holds a `&mut World` while this scope runs.
+    //
+    // `SchemaSource::Traced` builds its own batch per cluster below via
+    // `schema_trace::traced_record_batch_for_cluster` and never looks at `cluster_arrays`, so skip
+    // this fan-out entirely in that case.
+    let cluster_arrays: Vec<Result<Vec<(String, ArrayRef)>, ParquetError>> =
+        if config.schema_inference != SchemaSource::Traced {
+            let world_cell = world.as_unsafe_world_cell_readonly();
+            let record_writers = &config.record_writers;
+            let pool = bevy::tasks::ComputeTaskPool::get();
+            pool.scope(|scope| {
+                for (cluster, entities) in clusters.iter().zip(cluster_entities.iter()) {
+                    scope.spawn(async move {
+                        // SAFETY: this task only reads through `world_cell`, and `serialize_world`
+                        // takes no `&mut World` access anywhere while this scope is running.
+                        let world: &World = unsafe { world_cell.world() };
+
+                        let mut arrays = Vec::with_capacity(cluster.len() + 1);
+                        arrays.push(("entity_id".to_string(), create_uuid_array(entities)));
+
+                        for component in persisted_components(cluster) {
+                            arrays.extend(component_to_arrow_columns(
+                                world,
+                                entities,
+                                component,
+                                type_registry,
+                                record_writers,
+                            )?);
+                        }
+
+                        Ok(arrays)
+                    });
+                }
+            })
+            .into_iter()
+            .collect()
+        } else {
+            // Traced still needs one (unused) placeholder per cluster so the `zip` below lines up
+            // with `clusters`/`cluster_entities` — the per-cluster loop never reads `arrays` in the
+            // `Traced` branch.
+            clusters.iter().map(|_| Ok(Vec::new())).collect()
+        };
+
     // Process each cluster as a row group
-    for (i, cluster) in clusters.into_iter().enumerate() {
+    for (i, ((cluster, entities), arrays)) in clusters
+        .into_iter()
+        .zip(cluster_entities.into_iter())
+        .zip(cluster_arrays.into_iter())
+        .enumerate()
+    {
         println!("Processing cluster {:?}", cluster);
-        let props = WriterProperties::builder()
-            .set_compression(Compression::SNAPPY)
-            .build();
 
-        let schema = create_arrow_schema(&cluster, world, type_registry);
+        // `SchemaSource::Traced` infers the schema (and builds the batch) by tracing each
+        // entity's serialized row with `serde_arrow` instead of reflecting a representative
+        // instance; everything else keeps going through the existing reflection-based path.
+        let (schema, record_batch) = if config.schema_inference == SchemaSource::Traced {
+            let record_batch = schema_trace::traced_record_batch_for_cluster(
+                world,
+                &entities,
+                &cluster,
+                type_registry,
+            )?;
+            (record_batch.schema().as_ref().clone(), record_batch)
+        } else {
+            let schema =
+                create_arrow_schema(&cluster, &entities, world, type_registry, &config.record_writers);
+
+            // The fan-out above already built `arrays` from persisted_components(cluster), the
+            // same field list `create_arrow_schema` used, so no further filtering is needed here.
+            let arrays = arrays?;
+            println!("Created arrays: {:#?}", arrays);
+
+            let record_batch = RecordBatch::try_from_iter(arrays.into_iter())
+                .map_err(|e| ParquetError::ParquetWrite(e.to_string()))?;
+            (schema, record_batch)
+        };
         println!("Created schema: {:#?}", schema);
 
+        let column_names: Vec<String> = schema.fields().iter().map(|f| f.name().clone()).collect();
+        let writer_properties = compression::build_writer_properties(
+            &config.compression,
+            &recorder::cluster_signature(&cluster),
+            &column_names,
+        );
+
         let mut writer = {
             // For subsequent clusters, open the file in append mode
             let file = std::fs::File::create(format!(
@@ -120,52 +262,14 @@ pub fn serialize_world(world: &mut World) -> Result<(), ParquetError> {
                 })
             ))
             .map_err(ParquetError::Io)?;
-            ArrowWriter::try_new(
-                file,
-                Arc::new(schema),
-                Some(config.writer_properties.clone()),
-            )
+            ArrowWriter::try_new(file, Arc::new(schema), Some(writer_properties))
         }
         .map_err(|e| ParquetError::ParquetWrite(e.to_string()))?;
 
-        // Collect all entities that have these components
-        let mut entities: Vec<Entity> = Vec::new();
-        for entity in world.iter_entities() {
-            if cluster
-                .iter()
-                .all(|component_pair| world.get_by_id(entity.id(), component_pair.1).is_some())
-            {
-                entities.push(entity.id());
-            }
-        }
-
-        // Create arrays for each component
-        let mut arrays = Vec::new();
-
-        for type_id in cluster {
-            let array = component_to_arrow_array(world, &entities, type_id.clone(), type_registry)?;
-            arrays.push((type_id.0.clone(), array));
+        for (key, value) in metadata::footer_metadata(&config) {
+            writer.append_key_value_metadata(parquet::format::KeyValue::new(key, Some(value)));
         }
 
-        println!("Created arrays: {:#?}", arrays);
-        // TODO: Jank
-        arrays = arrays
-            .iter()
-            .filter_map(|a| match a {
-                (name, array) => {
-                    if name.contains("PhantomPersistTag") {
-                        None
-                    } else {
-                        Some((name.clone(), array.clone()))
-                    }
-                }
-            })
-            .collect();
-
-        // Create RecordBatch and write it
-        let record_batch = RecordBatch::try_from_iter(arrays.into_iter())
-            .map_err(|e| ParquetError::ParquetWrite(e.to_string()))?;
-
         println!("Writing record batch");
         println!("{:?}", record_batch);
 
@@ -178,11 +282,186 @@ pub fn serialize_world(world: &mut World) -> Result<(), ParquetError> {
             .map_err(|e| ParquetError::ParquetWrite(e.to_string()))?;
     }
 
+    if config.serialize_resources {
+        serialize_resources(world, type_registry, &config)?;
+    }
+
+    Ok(())
+}
+
+/// Emits `{output_path}_resources.parquet`: a single-row table with one column per reflected
+/// resource (any type registered with `ReflectResource`), using the same typed array/schema code
+/// path as components. Resources are singletons, so unlike the entity clusters this is always
+/// exactly one row.
+fn serialize_resources(
+    world: &World,
+    type_registry: &TypeRegistry,
+    config: &ParquetConfig,
+) -> Result<(), ParquetError> {
+    let mut columns: Vec<(String, Box<dyn Reflect>)> = Vec::new();
+    for registration in type_registry.iter() {
+        let Some(reflect_resource) = registration.data::<ReflectResource>() else {
+            continue;
+        };
+        let Some(reflect) = reflect_resource.reflect(world) else {
+            continue;
+        };
+        let name = registration
+            .type_info()
+            .type_path()
+            .split("::")
+            .last()
+            .unwrap()
+            .to_string();
+        columns.push((name, reflect.clone_value()));
+    }
+
+    if columns.is_empty() {
+        println!("[Resource serialization] No reflected resources found, skipping");
+        return Ok(());
+    }
+
+    let fields: Vec<arrow::datatypes::Field> = columns
+        .iter()
+        .map(|(name, value)| {
+            arrow::datatypes::Field::new(name, serialization::arrow_type_for_reflect(value.as_ref()), true)
+        })
+        .collect();
+    let schema = arrow::datatypes::Schema::new(fields);
+
+    let arrays: Vec<(String, ArrayRef)> = columns
+        .into_iter()
+        .zip(schema.fields())
+        .map(|((name, value), field)| {
+            (name, reflect_values_to_array(&[Some(value)], field.data_type()))
+        })
+        .collect();
+
+    let record_batch = RecordBatch::try_from_iter(arrays)
+        .map_err(|e| ParquetError::ParquetWrite(e.to_string()))?;
+
+    let column_names: Vec<String> = schema.fields().iter().map(|f| f.name().clone()).collect();
+    let writer_properties =
+        compression::build_writer_properties(&config.compression, "resources", &column_names);
+
+    let file = std::fs::File::create(format!("{}_resources.parquet", config.output_path))
+        .map_err(ParquetError::Io)?;
+    let mut writer = ArrowWriter::try_new(file, Arc::new(schema), Some(writer_properties))
+        .map_err(|e| ParquetError::ParquetWrite(e.to_string()))?;
+
+    for (key, value) in metadata::footer_metadata(config) {
+        writer.append_key_value_metadata(parquet::format::KeyValue::new(key, Some(value)));
+    }
+
+    writer
+        .write(&record_batch)
+        .map_err(|e| ParquetError::ParquetWrite(e.to_string()))?;
+    writer
+        .close()
+        .map_err(|e| ParquetError::ParquetWrite(e.to_string()))?;
+
+    Ok(())
+}
+
+/// Loads a world previously written by `serialize_world` back into the ECS. Every cluster file
+/// for `path` is read back batch by batch; each row's `entity_id` column (see `create_uuid_array`)
+/// is used to merge components coming from different cluster files onto the same spawned
+/// `Entity`, and every other column is reconstructed via reflection and applied with
+/// `ReflectComponent`, mirroring how `DynamicScene` rematerializes a serialized scene.
+pub fn deserialize_world(world: &mut World, path: &str) -> Result<(), ParquetError> {
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+    use std::collections::HashMap;
+
+    let binding = world.resource::<AppTypeRegistry>().clone();
+    let type_registry = binding.read();
+
+    let mut entity_ids: HashMap<u64, Entity> = HashMap::new();
+
+    for file_path in deserialization::cluster_files(path)? {
+        let file = std::fs::File::open(&file_path).map_err(ParquetError::Io)?;
+        let reader = ParquetRecordBatchReaderBuilder::try_new(file)
+            .map_err(|e| ParquetError::ParquetWrite(e.to_string()))?
+            .build()
+            .map_err(|e| ParquetError::ParquetWrite(e.to_string()))?;
+
+        for batch in reader {
+            let batch = batch.map_err(|e| ParquetError::ParquetWrite(e.to_string()))?;
+            deserialization::apply_batch(world, &mut entity_ids, &type_registry, &batch, &file_path)?;
+        }
+    }
+
     Ok(())
 }
 
+/// Builds the one or more Parquet columns for a single component. If `component_info`'s type was
+/// registered in `record_writers` (via `#[derive(ParquetComponent)]`), each entity's instance is
+/// split into its derived columns per `ParquetFieldDescriptor`; otherwise falls back to the
+/// existing single-column reflection path (`component_to_arrow_array`).
+pub(crate) fn component_to_arrow_columns(
+    world: &World,
+    entities: &[Entity],
+    component_info: (String, ComponentId),
+    type_registry: &TypeRegistry,
+    record_writers: &record_writer::ParquetRecordWriterRegistry,
+) -> Result<Vec<(String, ArrayRef)>, ParquetError> {
+    let Some(type_id) = world
+        .components()
+        .get_info(component_info.1)
+        .and_then(|info| info.type_id())
+    else {
+        let column_name = component_info.0.split("::").last().unwrap().to_string();
+        return Ok(vec![(
+            column_name,
+            component_to_arrow_array(world, entities, component_info, type_registry)?,
+        )]);
+    };
+
+    let Some(entry) = record_writers.get(type_id) else {
+        let column_name = component_info.0.split("::").last().unwrap().to_string();
+        return Ok(vec![(
+            column_name,
+            component_to_arrow_array(world, entities, component_info, type_registry)?,
+        )]);
+    };
+
+    let descriptors = entry.parquet_fields();
+    let mut per_field_values: Vec<Vec<Option<Box<dyn Reflect>>>> =
+        descriptors.iter().map(|_| Vec::with_capacity(entities.len())).collect();
+
+    for &entity in entities {
+        let reflected = world.get_entity(entity).and_then(|entity_ref| {
+            type_registry
+                .get(type_id)
+                .and_then(|registration| registration.data::<ReflectComponent>())
+                .and_then(|reflect_component| reflect_component.reflect(entity_ref))
+        });
+
+        let values: Vec<Option<Box<dyn Reflect>>> = match reflected.and_then(|r| entry.parquet_values(r)) {
+            Some(values) => values.into_iter().map(Some).collect(),
+            None => descriptors.iter().map(|_| None).collect(),
+        };
+
+        for (column, value) in per_field_values.iter_mut().zip(values) {
+            column.push(value);
+        }
+    }
+
+    Ok(descriptors
+        .into_iter()
+        .zip(per_field_values)
+        .map(|(descriptor, values)| {
+            let data_type = values
+                .iter()
+                .find_map(|v| v.as_ref())
+                .map(|v| serialization::arrow_type_for_reflect(v.as_ref()))
+                .unwrap_or(DataType::Utf8);
+            (descriptor.name, reflect_values_to_array(&values, &data_type))
+        })
+        .collect())
+}
+
 // TODO: Eventually to be able to take ExportType hint from the component and
-fn component_to_arrow_array(
+pub(crate) fn component_to_arrow_array(
     world: &World,
     entities: &[Entity],
     component_info: (String, ComponentId),
@@ -199,172 +478,174 @@ fn component_to_arrow_array(
     .entered();
 
     info!("Starting component serialization");
-    let mut values = Vec::with_capacity(entities.len());
-    let components = world.components();
 
     // Process each entity with tracing instrumentation
     #[instrument(skip_all, fields(entity = ?entity))]
-    fn process_entity(
+    fn reflect_entity(
         entity: Entity,
         world: &World,
         component_info: &(String, ComponentId),
         type_registry: &TypeRegistry,
-        values: &mut Vec<String>,
-    ) {
+    ) -> Option<Box<dyn Reflect>> {
         info!("Processing entity");
 
         // Just skip things that are PhantomPersistTag like
         if component_info.0.contains("PhantomPersistTag") {
-            return;
+            return None;
         }
 
         let entity_ref = match world.get_entity(entity) {
             Some(e) => e,
             None => {
                 warn!("Entity not found in world");
-                return;
+                return None;
             }
         };
 
-        debug!(
-            component_count = entity_ref.archetype().components().count(),
-            "Entity component count"
-        );
-
         let reflect = world
             .components()
             .get_info(component_info.1)
-            .ok_or_else(|| {
-                error!("Failed to get component info for {}", component_info.0);
-                ParquetError::Serialization(format!(
-                    "Failed to get component info for {}",
-                    component_info.0
-                ))
-            })
-            .and_then(|info| {
-                info!(
-                    component_name = info.name(),
-                    component_type_id = ?info.type_id(),
-                    "Component info retrieved"
-                );
-                info.type_id().ok_or_else(|| {
-                    error!("Missing type ID for component {}", component_info.0);
-                    ParquetError::Serialization(format!(
-                        "Missing type ID for component {}",
-                        component_info.0
-                    ))
-                })
-            })
-            .and_then(|id| {
-                debug!(type_id = ?id, "Looking up type registration");
-                type_registry.get(id).ok_or_else(|| {
-                    error!(
-                        "Type ID {:?} not found in registry for component {}",
-                        id, component_info.0
-                    );
-                    ParquetError::Serialization(format!(
-                        "Type ID {:?} not found in registry for component {}",
-                        id, component_info.0
-                    ))
-                })
-            })
-            .and_then(|reg| {
-                debug!(
-                    type_registration = ?reg.type_info(),
-                    "Retrieved type registration"
-                );
-                reg.data::<ReflectComponent>().ok_or_else(|| {
-                    error!("No ReflectComponent data for {}", component_info.0);
-                    ParquetError::Serialization(format!(
-                        "No ReflectComponent data for {}",
-                        component_info.0
-                    ))
-                })
-            })
-            .and_then(|reflect| {
-                debug!("Reflecting component instance");
-                reflect.reflect(entity_ref).ok_or_else(|| {
-                    error!("Failed to reflect component on entity {:?}", entity);
-                    ParquetError::Serialization(format!(
-                        "Failed to reflect component on entity {:?}",
-                        entity
-                    ))
-                })
-            });
+            .ok_or_else(|| error!("Failed to get component info for {}", component_info.0))
+            .ok()
+            .and_then(|info| info.type_id())
+            .and_then(|id| type_registry.get(id))
+            .and_then(|reg| reg.data::<ReflectComponent>())
+            .and_then(|reflect| reflect.reflect(entity_ref));
 
         match reflect {
-            Ok(reflect) => {
-                let reflect_discrete = reflect.reflect_ref();
-                // debug!(reflection_type = ?reflect_discrete, "Reflected component");
-
-                let output_field = match reflect_discrete {
-                    ReflectRef::Struct(inner) => match inner.field("output") {
-                        Some(output) => format!("{:?}", output),
-                        None => {
-                            warn!("No output field found, using full struct");
-                            format!("{:?}", reflect)
-                        }
-                    },
-                    ReflectRef::Value(inner) => {
-                        debug!(value_type = ?inner.get_represented_type_info(), "Processing value type");
-                        format!("{:?}", inner)
-                    }
-                    ReflectRef::TupleStruct(inner) => {
-                        debug!(field_count = inner.field_len(), "Processing tuple struct");
-                        format!("{:?}", inner.field(0).unwrap())
-                    }
-                    ReflectRef::Tuple(inner) => {
-                        debug!(field_count = inner.field_len(), "Processing tuple");
-                        format!("{:?}", inner.field(0).unwrap())
-                    }
-                    ReflectRef::List(inner) => {
-                        debug!(item_count = inner.len(), "Processing list");
-                        format!(
-                            "[{}]",
-                            inner
-                                .iter()
-                                .map(|x| format!("{:?}", x))
-                                .collect::<Vec<_>>()
-                                .join(", ")
-                        )
-                    }
-                    ReflectRef::Array(inner) => {
-                        debug!(length = inner.len(), "Processing array");
-                        format!(
-                            "[{}]",
-                            inner
-                                .iter()
-                                .map(|x| format!("{:?}", x))
-                                .collect::<Vec<_>>()
-                                .join(", ")
-                        )
-                    }
-                    _ => {
-                        warn!("Unhandled reflection type");
-                        format!("{:?}", reflect)
-                    }
-                };
-
-                info!(output_length = output_field.len(), "Serialized value");
-                debug!(output_value = %output_field);
-                values.push(output_field);
-            }
-            Err(e) => {
-                warn!("Skipping entity due to error: {}", e);
+            Some(reflect) => Some(reflect.clone_value()),
+            None => {
+                warn!("Skipping entity due to missing reflection data");
+                None
             }
         }
     }
 
     info!(entity_count = entities.len(), "Processing entities");
-    for &entity in entities {
-        process_entity(entity, world, &component_info, type_registry, &mut values);
+    let values: Vec<Option<Box<dyn Reflect>>> = entities
+        .iter()
+        .map(|&entity| reflect_entity(entity, world, &component_info, type_registry))
+        .collect();
+
+    // Infer the column's Arrow type from the first reflected instance we found, matching the
+    // schema built by `create_arrow_schema`, then build a typed array instead of stringifying.
+    let data_type = values
+        .iter()
+        .find_map(|v| v.as_ref())
+        .map(|v| serialization::arrow_type_for_reflect(v.as_ref()))
+        .unwrap_or(DataType::Utf8);
+
+    info!(values_count = values.len(), ?data_type, "Completed component serialization");
+    Ok(reflect_values_to_array(&values, &data_type))
+}
+
+/// Builds an Arrow array from reflected component values according to `data_type`, recursing
+/// into `Struct`/`List` types so nested components produce nested Arrow arrays. A value that failed
+/// to reflect becomes a null. Anything that doesn't fit a native Arrow type falls back to a
+/// `{:?}`-formatted string, except a `String`, which is written as-is.
+pub(crate) fn reflect_values_to_array(
+    values: &[Option<Box<dyn Reflect>>],
+    data_type: &DataType,
+) -> ArrayRef {
+    fn downcast<T: Copy + 'static>(value: &dyn Reflect) -> Option<T> {
+        value.downcast_ref::<T>().copied()
     }
 
-    info!(
-        values_count = values.len(),
-        "Completed component serialization"
-    );
-    Ok(Arc::new(StringArray::from(values)) as ArrayRef)
+    macro_rules! build_primitive {
+        ($builder:ty, $ty:ty) => {{
+            let mut builder = <$builder>::with_capacity(values.len());
+            for value in values {
+                match value.as_ref().and_then(|v| downcast::<$ty>(v.as_ref())) {
+                    Some(x) => builder.append_value(x),
+                    None => builder.append_null(),
+                }
+            }
+            Arc::new(builder.finish()) as ArrayRef
+        }};
+    }
+
+    match data_type {
+        DataType::Float32 => build_primitive!(Float32Builder, f32),
+        DataType::Float64 => build_primitive!(Float64Builder, f64),
+        DataType::Int8 => build_primitive!(Int8Builder, i8),
+        DataType::Int16 => build_primitive!(Int16Builder, i16),
+        DataType::Int32 => build_primitive!(Int32Builder, i32),
+        DataType::Int64 => build_primitive!(Int64Builder, i64),
+        DataType::UInt8 => build_primitive!(UInt8Builder, u8),
+        DataType::UInt16 => build_primitive!(UInt16Builder, u16),
+        DataType::UInt32 => build_primitive!(UInt32Builder, u32),
+        DataType::UInt64 => build_primitive!(UInt64Builder, u64),
+        DataType::Boolean => build_primitive!(BooleanBuilder, bool),
+        DataType::Struct(fields) => {
+            let mut children: Vec<ArrayRef> = Vec::with_capacity(fields.len());
+            for (index, field) in fields.iter().enumerate() {
+                let child_values: Vec<Option<Box<dyn Reflect>>> = values
+                    .iter()
+                    .map(|v| {
+                        v.as_ref().and_then(|reflect| match reflect.reflect_ref() {
+                            ReflectRef::Struct(inner) => {
+                                inner.field_at(index).map(|f| f.clone_value())
+                            }
+                            _ => None,
+                        })
+                    })
+                    .collect();
+                children.push(reflect_values_to_array(&child_values, field.data_type()));
+            }
+            let validity = NullBuffer::from_iter(values.iter().map(|v| v.is_some()));
+            Arc::new(StructArray::new(fields.clone(), children, Some(validity))) as ArrayRef
+        }
+        DataType::List(item_field) => {
+            let mut offsets: Vec<i32> = Vec::with_capacity(values.len() + 1);
+            let mut flat_values: Vec<Option<Box<dyn Reflect>>> = Vec::new();
+            let mut validity: Vec<bool> = Vec::with_capacity(values.len());
+
+            offsets.push(0);
+            for value in values {
+                let items = value.as_ref().and_then(|reflect| match reflect.reflect_ref() {
+                    ReflectRef::List(inner) => Some(
+                        inner.iter().map(|item| Some(item.clone_value())).collect::<Vec<_>>(),
+                    ),
+                    ReflectRef::Array(inner) => Some(
+                        inner.iter().map(|item| Some(item.clone_value())).collect::<Vec<_>>(),
+                    ),
+                    _ => None,
+                });
+
+                match items {
+                    Some(items) => {
+                        validity.push(true);
+                        flat_values.extend(items);
+                    }
+                    None => validity.push(false),
+                }
+                offsets.push(flat_values.len() as i32);
+            }
+
+            let child_array = reflect_values_to_array(&flat_values, item_field.data_type());
+            Arc::new(ListArray::new(
+                item_field.clone(),
+                OffsetBuffer::new(offsets.into()),
+                child_array,
+                Some(NullBuffer::from(validity)),
+            )) as ArrayRef
+        }
+        // Utf8 and anything else we don't have a typed mapping for yet.
+        _ => {
+            // A `String` is written as-is (its `{:?}` form would add quotes), so
+            // `deserialization::reflect_value_from_array` reads back the same text.
+            let strings: Vec<Option<String>> = values
+                .iter()
+                .map(|v| {
+                    v.as_ref().map(|reflect| match reflect.as_ref().downcast_ref::<String>() {
+                        Some(text) => text.clone(),
+                        None => format!("{:?}", reflect),
+                    })
+                })
+                .collect();
+            Arc::new(StringArray::from(strings)) as ArrayRef
+        }
+    }
 }
 
 pub trait Hope {
@@ -504,6 +785,7 @@ impl<T: Debug> Report for T {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use arrow::array::{Array, Float32Array, UInt32Array};
 
     #[test]
     fn test_plugin_initialization() {
@@ -513,4 +795,55 @@ mod tests {
         assert!(app.world().contains_resource::<ParquetConfig>());
         assert!(app.world().contains_resource::<ParquetState>());
     }
+
+    #[derive(Component, Reflect, Default, ParquetComponent)]
+    #[reflect(Component)]
+    struct DerivedStat {
+        score: f32,
+        hits: u32,
+    }
+
+    // Regression test for a `#[derive(ParquetComponent)]` bug where the generated
+    // `parquet_values` double-boxed each field (`Box::new(Reflect::clone_value(..))`, which
+    // already returns `Box<dyn Reflect>`), so every `downcast_ref::<T>()` in
+    // `reflect_values_to_array` failed and a derived component's columns came out all-null.
+    #[test]
+    fn derived_component_round_trips_through_the_registry() {
+        let mut registry = ParquetRecordWriterRegistry::default();
+        registry.register::<DerivedStat>();
+
+        let mut app = App::new();
+        app.register_type::<DerivedStat>();
+        let entity = app
+            .world_mut()
+            .spawn(DerivedStat { score: 4.5, hits: 3 })
+            .id();
+
+        let type_registry = app.world().resource::<AppTypeRegistry>().clone();
+        let type_registry = type_registry.read();
+        let component_id = app.world().component_id::<DerivedStat>().unwrap();
+
+        let columns = component_to_arrow_columns(
+            app.world(),
+            &[entity],
+            ("DerivedStat".to_string(), component_id),
+            &type_registry,
+            &registry,
+        )
+        .unwrap();
+
+        assert_eq!(columns.len(), 2);
+
+        let score_array = columns[0]
+            .1
+            .as_any()
+            .downcast_ref::<Float32Array>()
+            .expect("score column should be a typed Float32Array, not all-null from a double-boxed Reflect value");
+        assert_eq!(score_array.null_count(), 0);
+        assert_eq!(score_array.value(0), 4.5);
+
+        let hits_array = columns[1].1.as_any().downcast_ref::<UInt32Array>().unwrap();
+        assert_eq!(hits_array.null_count(), 0);
+        assert_eq!(hits_array.value(0), 3);
+    }
 }
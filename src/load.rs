@@ -0,0 +1,249 @@
+//! `ParquetLoadPlugin`: the read-side counterpart to `serialize_world`/the `ParquetRecorder`.
+//! Reads cluster files written by this crate back into the `World`, reusing
+//! `deserialization::apply_batch` to merge rows onto entities by `entity_id` exactly like
+//! `deserialize_world` does for a one-shot load.
+//!
+//! Unlike `deserialize_world` (which reads every batch of every file before returning), this
+//! plugin keeps one open reader per cluster file across frames and pulls a bounded number of
+//! record batches per tick, so a capture far larger than memory can still be replayed without stalling
+//! the schedule. `load_world_async` offers the same row-group/column selection for callers who'd
+//! rather drive the read from an async task instead of a recurring system.
+
+use std::collections::HashMap;
+use std::ops::Range;
+use std::path::PathBuf;
+
+use bevy::prelude::*;
+use parquet::arrow::arrow_reader::{ParquetRecordBatchReader, ParquetRecordBatchReaderBuilder, ProjectionMask};
+
+use crate::{deserialization, ParquetError};
+
+/// Configuration for `ParquetLoadPlugin`. Mirrors the shape of `ParquetConfig`/`RecorderConfig`:
+/// a plain `Resource` the caller inserts before (or instead of) adding the plugin.
+#[derive(Clone, Resource)]
+pub struct ParquetLoadConfig {
+    /// Same path prefix `deserialize_world` takes (`ParquetConfig::output_path` on the write
+    /// side): every `{path}_*.parquet` sibling file is loaded.
+    pub path: String,
+    /// Restricts each cluster file to this row-group range instead of reading the whole file.
+    /// `None` reads every row group.
+    pub row_group_range: Option<Range<usize>>,
+    /// Restricts each cluster file to these columns (plus `entity_id`, which is always kept so
+    /// rows can still be merged onto the right entity). `None` reads every column.
+    pub columns: Option<Vec<String>>,
+    /// How many `RecordBatch`es to pull per cluster file, per tick. Keeps a single frame's work
+    /// bounded regardless of how large the capture is; raise it to load faster at the cost of a
+    /// longer frame.
+    pub batches_per_frame: u32,
+}
+
+impl Default for ParquetLoadConfig {
+    fn default() -> Self {
+        Self {
+            path: "./".to_string(),
+            row_group_range: None,
+            columns: None,
+            batches_per_frame: 1,
+        }
+    }
+}
+
+/// One cluster file's open reader. `ParquetRecordBatchReader` is a plain synchronous `Iterator`,
+/// so "streaming across frames" is just not draining it in one go.
+struct LoadCursor {
+    reader: ParquetRecordBatchReader,
+}
+
+/// Tracks in-progress loads started by `load_tick`. Internal to the plugin; unlike `ParquetState`
+/// there's nothing here a caller needs to read back.
+#[derive(Default, Resource)]
+struct ParquetLoadState {
+    cursors: HashMap<PathBuf, LoadCursor>,
+    /// Cluster files already fully drained, so a finished load doesn't keep re-scanning the
+    /// directory for files to open every tick.
+    finished: std::collections::HashSet<PathBuf>,
+    /// `entity_id -> Entity` map shared by every cluster file, so the same logical entity is
+    /// merged onto one `Entity` across files, as in `deserialize_world`.
+    entity_ids: HashMap<u64, Entity>,
+}
+
+pub struct ParquetLoadPlugin;
+
+impl Plugin for ParquetLoadPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ParquetLoadState>()
+            .add_systems(Update, load_tick);
+    }
+}
+
+fn open_reader(
+    file_path: &std::path::Path,
+    config: &ParquetLoadConfig,
+) -> Result<ParquetRecordBatchReader, ParquetError> {
+    let file = std::fs::File::open(file_path).map_err(ParquetError::Io)?;
+    let mut builder = ParquetRecordBatchReaderBuilder::try_new(file)
+        .map_err(|e| ParquetError::ParquetWrite(e.to_string()))?;
+
+    if let Some(columns) = &config.columns {
+        let schema_descr = builder.parquet_schema();
+        let indices: Vec<usize> = schema_descr
+            .columns()
+            .iter()
+            .enumerate()
+            .filter(|(_, column)| {
+                let root = &column.path().parts()[0];
+                root == "entity_id" || columns.iter().any(|c| c == root)
+            })
+            .map(|(index, _)| index)
+            .collect();
+        builder = builder.with_projection(ProjectionMask::leaves(schema_descr, indices));
+    }
+
+    if let Some(range) = &config.row_group_range {
+        builder = builder.with_row_groups(range.clone().collect());
+    }
+
+    builder
+        .build()
+        .map_err(|e| ParquetError::ParquetWrite(e.to_string()))
+}
+
+/// Bevy system added by `ParquetLoadPlugin`. No-ops when `ParquetLoadConfig` hasn't been inserted.
+/// Opens a `LoadCursor` for every cluster file under `ParquetLoadConfig::path` the first time it's
+/// seen, then each tick pulls up to `batches_per_frame` batches from every still-open cursor.
+fn load_tick(world: &mut World) {
+    let Some(config) = world.get_resource::<ParquetLoadConfig>().cloned() else {
+        return;
+    };
+
+    let Ok(cluster_files) = deserialization::cluster_files(&config.path) else {
+        return;
+    };
+
+    {
+        let mut state = world.resource_mut::<ParquetLoadState>();
+        for file_path in &cluster_files {
+            if state.cursors.contains_key(file_path) || state.finished.contains(file_path) {
+                continue;
+            }
+            match open_reader(file_path, &config) {
+                Ok(reader) => {
+                    state.cursors.insert(file_path.clone(), LoadCursor { reader });
+                }
+                Err(e) => {
+                    tracing::error!(file = ?file_path, "Failed to open load cursor: {e}");
+                    state.finished.insert(file_path.clone());
+                }
+            }
+        }
+    }
+
+    let binding = world.resource::<AppTypeRegistry>().clone();
+    let type_registry = binding.read();
+
+    let open_files: Vec<PathBuf> = world
+        .resource::<ParquetLoadState>()
+        .cursors
+        .keys()
+        .cloned()
+        .collect();
+
+    for file_path in open_files {
+        for _ in 0..config.batches_per_frame {
+            // Pull the next batch out from under the resource before touching `world` mutably
+            // again, since `apply_batch` needs `&mut World` for `entity_for_id`/`entity_mut`.
+            let next_batch = {
+                let mut state = world.resource_mut::<ParquetLoadState>();
+                let Some(cursor) = state.cursors.get_mut(&file_path) else {
+                    break;
+                };
+                cursor.reader.next()
+            };
+
+            let Some(batch) = next_batch else {
+                // Exhausted: drop the cursor (closing the file) and stop trying this file.
+                let mut state = world.resource_mut::<ParquetLoadState>();
+                state.cursors.remove(&file_path);
+                state.finished.insert(file_path.clone());
+                break;
+            };
+
+            let batch = match batch {
+                Ok(batch) => batch,
+                Err(e) => {
+                    tracing::error!(file = ?file_path, "Failed to read batch: {e}");
+                    continue;
+                }
+            };
+
+            let mut entity_ids =
+                std::mem::take(&mut world.resource_mut::<ParquetLoadState>().entity_ids);
+
+            if let Err(e) =
+                deserialization::apply_batch(world, &mut entity_ids, &type_registry, &batch, &file_path)
+            {
+                tracing::error!(file = ?file_path, "Failed to apply batch: {e}");
+            }
+
+            world.resource_mut::<ParquetLoadState>().entity_ids = entity_ids;
+        }
+    }
+}
+
+/// Async counterpart of `load_tick`/`deserialize_world`, built on `parquet`'s async Arrow reader
+/// so a caller can drive a load from an IO task (e.g. `bevy::tasks::IoTaskPool`) without blocking
+/// the main schedule at all, at the cost of needing `&mut World` for the whole await (there's no
+/// way to fan this one out the way `serialize_world` parallelizes array building, since every row has to
+/// land on the same `World`).
+///
+/// Driving a `tokio::fs::File`-backed `ParquetRecordBatchStreamBuilder` requires a Tokio runtime
+/// to be running; wire this up behind whatever async executor the host app already uses (e.g. via
+/// `bevy_tokio_tasks`) rather than spinning one up here.
+pub async fn load_world_async(world: &mut World, config: &ParquetLoadConfig) -> Result<(), ParquetError> {
+    use futures::StreamExt;
+    use parquet::arrow::async_reader::ParquetRecordBatchStreamBuilder;
+
+    let binding = world.resource::<AppTypeRegistry>().clone();
+    let type_registry = binding.read();
+
+    let mut entity_ids: HashMap<u64, Entity> = HashMap::new();
+
+    for file_path in deserialization::cluster_files(&config.path)? {
+        let file = tokio::fs::File::open(&file_path)
+            .await
+            .map_err(ParquetError::Io)?;
+        let mut builder = ParquetRecordBatchStreamBuilder::new(file)
+            .await
+            .map_err(|e| ParquetError::ParquetWrite(e.to_string()))?;
+
+        if let Some(columns) = &config.columns {
+            let schema_descr = builder.parquet_schema();
+            let indices: Vec<usize> = schema_descr
+                .columns()
+                .iter()
+                .enumerate()
+                .filter(|(_, column)| {
+                    let root = &column.path().parts()[0];
+                    root == "entity_id" || columns.iter().any(|c| c == root)
+                })
+                .map(|(index, _)| index)
+                .collect();
+            builder = builder.with_projection(ProjectionMask::leaves(schema_descr, indices));
+        }
+
+        if let Some(range) = &config.row_group_range {
+            builder = builder.with_row_groups(range.clone().collect());
+        }
+
+        let mut stream = builder
+            .build()
+            .map_err(|e| ParquetError::ParquetWrite(e.to_string()))?;
+
+        while let Some(batch) = stream.next().await {
+            let batch = batch.map_err(|e| ParquetError::ParquetWrite(e.to_string()))?;
+            deserialization::apply_batch(world, &mut entity_ids, &type_registry, &batch, &file_path)?;
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,29 @@
+use crate::ParquetConfig;
+
+/// Key-value pairs to stamp into a written file's Parquet footer: a few auto-generated
+/// provenance pairs (crate version, export timestamp) followed by whatever the user set on
+/// `ParquetConfig::key_value_metadata`, so downstream readers can validate a file's origin
+/// without a side-channel.
+pub(crate) fn footer_metadata(config: &ParquetConfig) -> Vec<(String, String)> {
+    let exported_at = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+
+    let mut pairs = vec![
+        (
+            "bevy_parquet.crate_version".to_string(),
+            env!("CARGO_PKG_VERSION").to_string(),
+        ),
+        (
+            "bevy_parquet.exported_at_unix_secs".to_string(),
+            exported_at.to_string(),
+        ),
+    ];
+
+    if let Some(user_pairs) = &config.key_value_metadata {
+        pairs.extend(user_pairs.iter().cloned());
+    }
+
+    pairs
+}
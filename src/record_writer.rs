@@ -0,0 +1,117 @@
+use arrow::{
+    array::{ArrayRef, RecordBatch},
+    datatypes::{Field, Schema},
+};
+use bevy::reflect::Reflect;
+use std::any::TypeId;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::{serialization::arrow_type_for_reflect, ParquetError};
+
+/// One column produced by a `#[derive(ParquetComponent)]` impl, in struct-field declaration
+/// order (after applying any `#[parquet(rename = "...")]`/`#[parquet(skip)]`).
+pub struct ParquetFieldDescriptor {
+    pub name: String,
+    pub nullable: bool,
+}
+
+/// Implemented by `#[derive(ParquetComponent)]`. Gives a component explicit control over its
+/// Parquet columns instead of going through the reflection-based schema inference in
+/// `serialization::create_arrow_schema`, the same way `parquet_derive`'s `ParquetRecordWriter`
+/// lets a plain struct describe its own row shape.
+///
+/// Register a derived type with `ParquetRecordWriterRegistry::register` (via
+/// `ParquetConfig::record_writers`) to have `serialize_world`/the recorder use this instead of
+/// reflection for that component's columns; otherwise use `record_batch_for` directly to write a
+/// derived type's instances as their own standalone table.
+pub trait ParquetRecordWriter {
+    /// Column descriptors in declaration order.
+    fn parquet_fields() -> Vec<ParquetFieldDescriptor>;
+    /// This instance's values, one per descriptor returned by `parquet_fields`, in the same
+    /// order.
+    fn parquet_values(&self) -> Vec<Box<dyn Reflect>>;
+}
+
+/// Type-erased bridge from a concrete `ParquetRecordWriter` impl to the cluster pipeline:
+/// `ParquetRecordWriter::parquet_fields` is a static method with no `&self`, so it isn't
+/// dyn-compatible on its own, and `parquet_values` needs a `&dyn Reflect` to be called without
+/// knowing the concrete type. `ParquetRecordWriterRegistry` stores one of these per registered
+/// component, keyed by `TypeId`.
+pub(crate) trait ErasedParquetRecordWriter: Send + Sync {
+    fn parquet_fields(&self) -> Vec<ParquetFieldDescriptor>;
+    fn parquet_values(&self, value: &dyn Reflect) -> Option<Vec<Box<dyn Reflect>>>;
+}
+
+struct ErasedWriter<T>(std::marker::PhantomData<fn() -> T>);
+
+impl<T: ParquetRecordWriter + Reflect> ErasedParquetRecordWriter for ErasedWriter<T> {
+    fn parquet_fields(&self) -> Vec<ParquetFieldDescriptor> {
+        T::parquet_fields()
+    }
+
+    fn parquet_values(&self, value: &dyn Reflect) -> Option<Vec<Box<dyn Reflect>>> {
+        Some(value.as_any().downcast_ref::<T>()?.parquet_values())
+    }
+}
+
+/// Maps a component's `TypeId` to its `#[derive(ParquetComponent)]`-generated column layout, so
+/// `serialization::create_arrow_schema` and the array-building code in `lib.rs`/`recorder.rs` can
+/// use a derived type's explicit columns for a component instead of the one-field-per-component
+/// reflection path. Empty by default; populate it with `register` and set it on
+/// `ParquetConfig::record_writers`.
+#[derive(Clone, Default)]
+pub struct ParquetRecordWriterRegistry {
+    entries: HashMap<TypeId, Arc<dyn ErasedParquetRecordWriter>>,
+}
+
+impl ParquetRecordWriterRegistry {
+    /// Registers `T`'s derived column layout so the cluster pipeline uses it for any component of
+    /// this type instead of reflecting it field-by-field.
+    pub fn register<T: ParquetRecordWriter + Reflect>(&mut self) -> &mut Self {
+        self.entries
+            .insert(TypeId::of::<T>(), Arc::new(ErasedWriter::<T>(std::marker::PhantomData)));
+        self
+    }
+
+    pub(crate) fn get(&self, type_id: TypeId) -> Option<&Arc<dyn ErasedParquetRecordWriter>> {
+        self.entries.get(&type_id)
+    }
+}
+
+/// Builds a single `RecordBatch` from a slice of `ParquetRecordWriter` instances, inferring each
+/// column's Arrow type from the first non-null value seen for it via `arrow_type_for_reflect`
+/// (same rule `component_to_arrow_array` uses for reflected components).
+pub fn record_batch_for<T: ParquetRecordWriter>(rows: &[T]) -> Result<RecordBatch, ParquetError> {
+    let descriptors = T::parquet_fields();
+    let mut columns: Vec<Vec<Option<Box<dyn Reflect>>>> =
+        descriptors.iter().map(|_| Vec::with_capacity(rows.len())).collect();
+
+    for row in rows {
+        for (column, value) in columns.iter_mut().zip(row.parquet_values()) {
+            column.push(Some(value));
+        }
+    }
+
+    let fields: Vec<Field> = descriptors
+        .iter()
+        .zip(columns.iter())
+        .map(|(descriptor, values)| {
+            let data_type = values
+                .iter()
+                .find_map(|v| v.as_ref())
+                .map(|v| arrow_type_for_reflect(v.as_ref()))
+                .unwrap_or(arrow::datatypes::DataType::Utf8);
+            Field::new(&descriptor.name, data_type, descriptor.nullable)
+        })
+        .collect();
+
+    let arrays: Vec<ArrayRef> = fields
+        .iter()
+        .zip(columns.into_iter())
+        .map(|(field, values)| crate::reflect_values_to_array(&values, field.data_type()))
+        .collect();
+
+    RecordBatch::try_new(Arc::new(Schema::new(fields)), arrays)
+        .map_err(|e| ParquetError::ParquetWrite(e.to_string()))
+}
@@ -0,0 +1,247 @@
+use {
+    crate::{
+        component_to_arrow_columns,
+        serialization::{
+            create_arrow_schema, create_uuid_array, detect_component_clusters, persisted_components,
+        },
+        writer::ParquetWriter,
+        FlushPolicy, Hope, ParquetConfig, ParquetState,
+    },
+    arrow::{
+        array::{ArrayRef, RecordBatch, UInt64Array},
+        datatypes::{DataType, Field, Schema},
+    },
+    bevy::{app::AppExit, ecs::component::ComponentId, prelude::*},
+    std::sync::Arc,
+};
+
+/// Derives a stable key for a cluster's component set so the same logical cluster keeps
+/// appending to the same open `ParquetWriter` across ticks, regardless of the order
+/// `detect_component_clusters` happens to return its members in.
+pub(crate) fn cluster_signature(cluster: &[(String, ComponentId)]) -> String {
+    let mut names: Vec<&str> = cluster.iter().map(|(name, _)| name.as_str()).collect();
+    names.sort_unstable();
+    names.join("|")
+}
+
+/// Bevy system added by `ParquetPlugin` that appends a snapshot batch to each cluster's open
+/// writer whenever a tracked component changed since the last snapshot, or every
+/// `interval_frames` ticks if configured, so idle frames cost nothing. Row groups are closed per
+/// `RecorderConfig::flush_policy`. No-ops when `ParquetConfig::recorder` is unset.
+pub(crate) fn record_tick(world: &mut World) {
+    let Some(config) = world.get_resource::<ParquetConfig>().cloned() else {
+        return;
+    };
+    let Some(recorder_config) = config.recorder.clone() else {
+        return;
+    };
+
+    let this_run = world.change_tick();
+    let clusters = detect_component_clusters(world, config.cluster_merge_threshold);
+
+    let binding = world.resource::<AppTypeRegistry>().clone();
+    let type_registry = binding.read();
+
+    for cluster in clusters {
+        let signature = cluster_signature(&cluster);
+
+        let entities: Vec<Entity> = world
+            .iter_entities()
+            .filter(|entity| {
+                cluster
+                    .iter()
+                    .all(|(_, component_id)| world.get_by_id(entity.id(), *component_id).is_some())
+            })
+            .map(|entity| entity.id())
+            .collect();
+
+        if entities.is_empty() {
+            continue;
+        }
+
+        let last_run = world
+            .resource::<ParquetState>()
+            .recorder_ticks
+            .get(&signature)
+            .copied();
+
+        let changed_since_last_run = last_run
+            .map(|last_run| {
+                entities.iter().any(|&entity| {
+                    cluster.iter().any(|(_, component_id)| {
+                        world
+                            .get_entity(entity)
+                            .and_then(|entity_ref| entity_ref.get_change_ticks_by_id(*component_id))
+                            .map(|ticks| ticks.is_changed(last_run, this_run))
+                            .unwrap_or(false)
+                    })
+                })
+            })
+            .unwrap_or(true); // Nothing recorded yet for this cluster: always take the first snapshot.
+
+        // Count record_tick calls since this cluster's last snapshot, not World::change_tick():
+        // that's a world-wide counter shared by every change-detection consumer and can advance
+        // by more than one per frame, so `% interval_frames` against it doesn't mean "every N
+        // frames".
+        let frames_since_snapshot = {
+            let mut state = world.resource_mut::<ParquetState>();
+            let counter = state
+                .recorder_frames_since_snapshot
+                .entry(signature.clone())
+                .or_insert(0);
+            *counter += 1;
+            *counter
+        };
+
+        let due_by_interval = recorder_config
+            .interval_frames
+            .map(|interval| interval > 0 && frames_since_snapshot >= interval)
+            .unwrap_or(false);
+
+        if !changed_since_last_run && !due_by_interval {
+            continue;
+        }
+
+        // Build from the same persisted_components() field list create_arrow_schema (below) uses,
+        // so the columns built here and the schema describing them never disagree on count.
+        let persisted = persisted_components(&cluster);
+
+        let mut arrays: Vec<(String, ArrayRef)> = Vec::with_capacity(persisted.len() + 2);
+        arrays.push(("entity_id".to_string(), create_uuid_array(&entities)));
+        for component in &persisted {
+            match component_to_arrow_columns(
+                world,
+                &entities,
+                component.clone(),
+                &type_registry,
+                &config.record_writers,
+            ) {
+                Ok(columns) => arrays.extend(columns),
+                Err(e) => {
+                    tracing::error!("Failed to build recorder column for {}: {}", component.0, e);
+                    continue;
+                }
+            }
+        }
+        let tick_values: Vec<u64> = std::iter::repeat(this_run.get() as u64)
+            .take(entities.len())
+            .collect();
+        arrays.push((
+            "tick".to_string(),
+            Arc::new(UInt64Array::from(tick_values)) as ArrayRef,
+        ));
+
+        let record_batch = match RecordBatch::try_from_iter(arrays) {
+            Ok(batch) => batch,
+            Err(e) => {
+                tracing::error!("Failed to build recorder batch for cluster {signature}: {e}");
+                continue;
+            }
+        };
+
+        let needs_new_writer = !world
+            .resource::<ParquetState>()
+            .recorder_writers
+            .contains_key(&signature);
+
+        // Schema is fixed when the writer opens at the first snapshot, so build it (which needs a `&World` borrow)
+        // before taking `ParquetState` mutably below.
+        let new_writer = if needs_new_writer {
+            let mut fields: Vec<Field> = create_arrow_schema(
+                &cluster,
+                &entities,
+                world,
+                &type_registry,
+                &config.record_writers,
+            )
+                .fields()
+                .iter()
+                .map(|field| field.as_ref().clone())
+                .collect();
+            fields.push(Field::new("tick", DataType::UInt64, false));
+            let column_names: Vec<String> = fields.iter().map(|f| f.name().clone()).collect();
+            let schema = Arc::new(Schema::new(fields));
+
+            let path = format!(
+                "{}_{}.parquet",
+                config.output_path,
+                signature.replace('|', "_")
+            );
+            let writer_properties =
+                crate::compression::build_writer_properties(&config.compression, &signature, &column_names);
+            match ParquetWriter::new(path, schema, writer_properties) {
+                Ok(mut writer) => {
+                    for (key, value) in crate::metadata::footer_metadata(&config) {
+                        writer.append_key_value_metadata(key, value);
+                    }
+                    Some(writer)
+                }
+                Err(e) => {
+                    tracing::error!("Failed to open recorder writer for cluster {signature}: {e}");
+                    continue;
+                }
+            }
+        } else {
+            None
+        };
+
+        let batch_bytes = record_batch.get_array_memory_size();
+
+        let mut state = world.resource_mut::<ParquetState>();
+        if let Some(writer) = new_writer {
+            state.recorder_writers.insert(signature.clone(), writer);
+        }
+        if let Some(writer) = state.recorder_writers.get_mut(&signature) {
+            writer
+                .write_batch(&record_batch)
+                .complain_msg("Failed to append recorder row group")
+                .hope();
+        }
+        state.recorder_ticks.insert(signature.clone(), this_run);
+        state.recorder_frames_since_snapshot.insert(signature.clone(), 0);
+
+        let snapshots_since_flush = state
+            .recorder_snapshots_since_flush
+            .entry(signature.clone())
+            .or_insert(0);
+        *snapshots_since_flush += 1;
+        let bytes_since_flush = state
+            .recorder_bytes_since_flush
+            .entry(signature.clone())
+            .or_insert(0);
+        *bytes_since_flush += batch_bytes;
+
+        let due_to_flush = match recorder_config.flush_policy {
+            Some(FlushPolicy::EveryFrames(frames)) => frames > 0 && *snapshots_since_flush >= frames,
+            Some(FlushPolicy::RowGroupBytes(bytes)) => bytes > 0 && *bytes_since_flush >= bytes,
+            None => false,
+        };
+
+        if due_to_flush {
+            if let Some(writer) = state.recorder_writers.get_mut(&signature) {
+                writer
+                    .flush()
+                    .complain_msg("Failed to flush recorder row group")
+                    .hope();
+            }
+            state.recorder_snapshots_since_flush.insert(signature.clone(), 0);
+            state.recorder_bytes_since_flush.insert(signature, 0);
+        }
+    }
+}
+
+/// Bevy system added by `ParquetPlugin` that finalizes every open recorder writer on `AppExit`
+/// so the Parquet footer gets written before the process ends.
+pub(crate) fn finalize_on_exit(mut exit_events: EventReader<AppExit>, mut state: ResMut<ParquetState>) {
+    if exit_events.read().next().is_none() {
+        return;
+    }
+
+    for (signature, writer) in state.recorder_writers.drain() {
+        writer
+            .finalize()
+            .map(|_metadata| ())
+            .complain_msg(&format!("Failed to finalize recorder writer for cluster {signature}"))
+            .hope();
+    }
+}
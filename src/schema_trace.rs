@@ -0,0 +1,82 @@
+use arrow::array::RecordBatch;
+use arrow::datatypes::Schema;
+use bevy::ecs::component::ComponentId;
+use bevy::prelude::*;
+use bevy::reflect::{ReflectComponent, ReflectSerialize, TypeRegistry};
+use serde_arrow::schema::{SchemaLike, TracingOptions};
+use std::sync::Arc;
+
+use crate::serialization::persisted_components;
+use crate::ParquetError;
+
+/// `SchemaSource::Traced` counterpart to `serialization::create_arrow_schema` +
+/// `component_to_arrow_array`: instead of reflecting one representative instance per component
+/// and mapping its `Reflect` value to an Arrow type by hand, every entity's row is serialized to
+/// a `serde_json::Value` via `ReflectSerialize`, and `serde_arrow` traces the Arrow schema (and
+/// builds the columns) from those sample rows directly.
+pub(crate) fn traced_record_batch_for_cluster(
+    world: &World,
+    entities: &[Entity],
+    cluster: &[(String, ComponentId)],
+    type_registry: &TypeRegistry,
+) -> Result<RecordBatch, ParquetError> {
+    let rows: Vec<serde_json::Value> = entities
+        .iter()
+        .map(|entity| {
+            let mut row = serde_json::Map::new();
+            row.insert(
+                "entity_id".to_string(),
+                serde_json::Value::String(entity.to_bits().to_string()),
+            );
+
+            // Same `persisted_components` filter the `Reflected` path uses, so the two
+            // `SchemaSource` variants agree on columns for the same cluster instead of `Traced`
+            // alone emitting an all-null `PhantomPersistTag` column.
+            for (name, component_id) in &persisted_components(cluster) {
+                let column_name = name.split("::").last().unwrap().to_string();
+                let value = serialize_component(world, *entity, *component_id, type_registry)
+                    .unwrap_or(serde_json::Value::Null);
+                row.insert(column_name, value);
+            }
+
+            serde_json::Value::Object(row)
+        })
+        .collect();
+
+    let tracing_options = TracingOptions::default()
+        .allow_null_fields(true)
+        .guess_dates(true);
+    let fields = Vec::<arrow::datatypes::FieldRef>::from_samples(&rows, tracing_options)
+        .map_err(|e| ParquetError::Serialization(e.to_string()))?;
+    let arrays = serde_arrow::to_arrow(&fields, &rows)
+        .map_err(|e| ParquetError::Serialization(e.to_string()))?;
+
+    RecordBatch::try_new(
+        Arc::new(Schema::new(
+            fields.iter().map(|f| f.as_ref().clone()).collect::<Vec<_>>(),
+        )),
+        arrays,
+    )
+    .map_err(|e| ParquetError::ParquetWrite(e.to_string()))
+}
+
+/// Serializes one entity's component to JSON via `ReflectSerialize`. Returns `None` for
+/// components that don't register `ReflectSerialize`, or that the entity doesn't carry, matching
+/// `representative_instance`'s "fall back and keep going" behaviour rather than erroring out.
+fn serialize_component(
+    world: &World,
+    entity: Entity,
+    component_id: ComponentId,
+    type_registry: &TypeRegistry,
+) -> Option<serde_json::Value> {
+    let type_id = world.components().get_info(component_id)?.type_id()?;
+    let registration = type_registry.get(type_id)?;
+    let reflect_component = registration.data::<ReflectComponent>()?;
+    let reflect_serialize = registration.data::<ReflectSerialize>()?;
+
+    let entity_ref = world.get_entity(entity)?;
+    let value = reflect_component.reflect(entity_ref)?;
+    let serializable = reflect_serialize.get_serializable(value);
+
+    serde_json::to_value(&serializable).ok()
+}
@@ -1,14 +1,24 @@
 use arrow::array::{ArrayRef, StringArray};
-use arrow::datatypes::{DataType, Field, Schema};
+use arrow::datatypes::{DataType, Field, Fields, Schema};
 use bevy::ecs::component::ComponentId;
 use bevy::prelude::*;
-use bevy::reflect::{GetTypeRegistration, TypeRegistry};
+use bevy::reflect::{GetTypeRegistration, Reflect, ReflectRef, TypeRegistry};
 use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
-/// Detects natural component clusters in the world
-pub(crate) fn detect_component_clusters(world: &World) -> Vec<Vec<(String, ComponentId)>> {
-    let mut entity_components: HashMap<Entity, HashSet<(String, ComponentId)>> = HashMap::new();
+use crate::record_writer::{ErasedParquetRecordWriter, ParquetRecordWriterRegistry};
+
+/// Detects natural component clusters in the world.
+///
+/// This is deterministic in two stages: entities are first grouped by their *exact*
+/// component-set signature (a sorted `Vec<ComponentId>`), then signatures are visited in sorted
+/// order and merged into a cluster whenever their Jaccard similarity `|A∩B| / |A∪B|` exceeds
+/// `merge_threshold`. A merge always takes the **union** of the two groups' columns, never the
+/// intersection, so a component already seen is never silently dropped from the output.
+pub(crate) fn detect_component_clusters(
+    world: &World,
+    merge_threshold: f32,
+) -> Vec<Vec<(String, ComponentId)>> {
     let mut components_we_care_about = vec![];
 
     // First pass: collect all components per things reflected in TypeRegistry into a list we care about
@@ -23,82 +33,267 @@ pub(crate) fn detect_component_clusters(world: &World) -> Vec<Vec<(String, Compo
             components_we_care_about.push(type_id);
         });
 
-    // 1.5 pass: collect all components per entity
+    // Second pass: group entities by their exact component-set signature.
+    let mut base_groups: HashMap<Vec<ComponentId>, HashSet<(String, ComponentId)>> = HashMap::new();
     for entity in world.iter_entities() {
         let mut components = HashSet::new();
         entity.archetype().components().for_each(|component_id| {
             if let Some(component_info) = world.components().get_info(component_id) {
-                if components_we_care_about.contains(&component_info.type_id().unwrap()) {
-                    components.insert((component_info.name().to_string(), component_info.id()));
+                if let Some(type_id) = component_info.type_id() {
+                    if components_we_care_about.contains(&type_id) {
+                        components.insert((component_info.name().to_string(), component_info.id()));
+                    }
                 }
             }
         });
 
-        // Skip if this entoity has no component we care about
+        // Skip if this entity has no component we care about
         if components.is_empty() {
             continue;
         }
-        entity_components.insert(entity.id(), components);
-    }
 
-    // Second pass: cluster similar component sets
-    let mut clusters: Vec<HashSet<(String, ComponentId)>> = Vec::new();
-    let mut processed_entities = HashSet::new();
+        let mut signature: Vec<ComponentId> = components.iter().map(|(_, id)| *id).collect();
+        signature.sort_unstable();
 
-    for (entity, components) in entity_components.iter() {
-        if processed_entities.contains(entity) {
-            continue;
-        }
+        base_groups
+            .entry(signature)
+            .or_default()
+            .extend(components);
+    }
 
-        let mut cluster = components.clone();
-        processed_entities.insert(*entity);
+    // Visit signatures in sorted order so cluster membership (and therefore output file
+    // contents) doesn't depend on HashMap iteration order.
+    let mut signatures: Vec<Vec<ComponentId>> = base_groups.keys().cloned().collect();
+    signatures.sort();
 
-        // Find all entities with similar component sets
-        for (other_entity, other_components) in entity_components.iter() {
-            if processed_entities.contains(other_entity) {
-                continue;
-            }
+    // Third pass: merge base groups whose ids are similar enough, keeping the union of columns.
+    let mut merged: Vec<(HashSet<ComponentId>, HashSet<(String, ComponentId)>)> = Vec::new();
+    for signature in signatures {
+        let columns = base_groups.remove(&signature).unwrap();
+        let ids: HashSet<ComponentId> = signature.into_iter().collect();
 
-            let intersection: HashSet<_> =
-                cluster.intersection(other_components).cloned().collect();
-            let union: HashSet<_> = cluster.union(other_components).cloned().collect();
+        let merge_target = merged.iter_mut().find(|(merged_ids, _)| {
+            let intersection = merged_ids.intersection(&ids).count();
+            let union = merged_ids.union(&ids).count();
+            union > 0 && intersection as f32 / union as f32 > merge_threshold
+        });
 
-            // If sets are similar enough (>80% overlap), merge them
-            if intersection.len() as f32 / union.len() as f32 > 0.8 {
-                cluster = intersection;
-                processed_entities.insert(*other_entity);
+        match merge_target {
+            Some((merged_ids, merged_columns)) => {
+                merged_ids.extend(ids);
+                merged_columns.extend(columns);
             }
-        }
-
-        if !cluster.is_empty() {
-            clusters.push(cluster);
+            None => merged.push((ids, columns)),
         }
     }
 
-    // Convert HashSets to Vecs
-    clusters
+    merged
         .into_iter()
-        .map(|set| set.into_iter().collect())
+        .map(|(_, columns)| {
+            let mut columns: Vec<(String, ComponentId)> = columns.into_iter().collect();
+            columns.sort_by(|a, b| a.1.cmp(&b.1));
+            columns
+        })
         .collect()
 }
 
-/// Creates an Arrow schema for a given set of components
-pub(crate) fn create_arrow_schema(components: &[(String, ComponentId)]) -> Schema {
+/// Components that actually become Parquet columns for a cluster: everything except
+/// `PhantomPersistTag`, which only exists as the filter marker `serialize_world` uses to decide
+/// which clusters are persisted at all (see its "TODO: This is rather stupid way..." comment) and
+/// was never meant to be written out as a column itself. `create_arrow_schema` and every array
+/// builder must derive their field list from this, not the raw cluster, or the schema and the
+/// `RecordBatch` built from it end up with a different number of columns.
+pub(crate) fn persisted_components(
+    cluster: &[(String, ComponentId)],
+) -> Vec<(String, ComponentId)> {
+    cluster
+        .iter()
+        .filter(|(name, _)| !name.contains("PhantomPersistTag"))
+        .cloned()
+        .collect()
+}
+
+/// Finds one entity (from `entities`, i.e. the exact rows about to be written) carrying
+/// `component_id` and clones its reflected value, to be used as a representative instance when
+/// inferring the Arrow `DataType` for that component.
+///
+/// Deliberately scoped to `entities` rather than the whole world: `component_to_arrow_array`
+/// infers its column's type from the same slice, and the two must agree on every column's type or
+/// the schema built here won't match the array it's later paired with.
+fn representative_instance(
+    world: &World,
+    entities: &[Entity],
+    component_id: ComponentId,
+    type_registry: &TypeRegistry,
+) -> Option<Box<dyn Reflect>> {
+    let type_id = world.components().get_info(component_id)?.type_id()?;
+    let registration = type_registry.get(type_id)?;
+    let reflect_component = registration.data::<ReflectComponent>()?;
+
+    entities
+        .iter()
+        .find_map(|&entity| {
+            world
+                .get_entity(entity)
+                .and_then(|entity_ref| reflect_component.reflect(entity_ref))
+        })
+        .map(|reflect| reflect.clone_value())
+}
+
+/// Infers the Arrow `DataType` for a reflected value, recursing into structs and lists so that
+/// nested components map onto nested Arrow `StructArray`/`ListArray` types. Falls back to `Utf8`
+/// for anything we don't know how to classify (enums, maps, opaque values, ...).
+pub(crate) fn arrow_type_for_reflect(value: &dyn Reflect) -> DataType {
+    match value.reflect_ref() {
+        ReflectRef::Value(inner) => {
+            match inner.get_represented_type_info().map(|info| info.type_path()) {
+                Some("f32") => DataType::Float32,
+                Some("f64") => DataType::Float64,
+                Some("i8") => DataType::Int8,
+                Some("i16") => DataType::Int16,
+                Some("i32") => DataType::Int32,
+                Some("i64") => DataType::Int64,
+                Some("u8") => DataType::UInt8,
+                Some("u16") => DataType::UInt16,
+                Some("u32") => DataType::UInt32,
+                Some("u64") => DataType::UInt64,
+                Some("bool") => DataType::Boolean,
+                _ => DataType::Utf8,
+            }
+        }
+        ReflectRef::Struct(inner) => {
+            let fields: Vec<Field> = (0..inner.field_len())
+                .filter_map(|i| {
+                    let name = inner.name_at(i)?;
+                    let field_value = inner.field_at(i)?;
+                    Some(Field::new(name, arrow_type_for_reflect(field_value), true))
+                })
+                .collect();
+            DataType::Struct(Fields::from(fields))
+        }
+        ReflectRef::List(inner) => {
+            let item_type = inner
+                .iter()
+                .next()
+                .map(arrow_type_for_reflect)
+                .unwrap_or(DataType::Utf8);
+            DataType::List(Arc::new(Field::new("item", item_type, true)))
+        }
+        ReflectRef::Array(inner) => {
+            let item_type = inner
+                .iter()
+                .next()
+                .map(arrow_type_for_reflect)
+                .unwrap_or(DataType::Utf8);
+            DataType::List(Arc::new(Field::new("item", item_type, true)))
+        }
+        // Tuples, tuple structs, enums, maps: no clean columnar mapping yet, keep as text.
+        _ => DataType::Utf8,
+    }
+}
+
+/// Creates an Arrow schema for a given set of components, reflecting one representative instance
+/// per component to pick a native Arrow type instead of stringifying everything.
+pub(crate) fn create_arrow_schema(
+    components: &[(String, ComponentId)],
+    entities: &[Entity],
+    world: &World,
+    type_registry: &TypeRegistry,
+    record_writers: &ParquetRecordWriterRegistry,
+) -> Schema {
     let mut fields = Vec::new();
 
+    // Stable join key: `Entity::to_bits` as a string, see `create_uuid_array`. Non-nullable and
+    // always first so every cluster file from the same `serialize_world` call can be joined on it.
+    fields.push(Field::new("entity_id", DataType::Utf8, false));
+
     // Add fields for each component
     // NOTE: Name here lokks something like "bevy::ecs::component::ComponentId"
     //       which is not what we want. This is jank but we are going to split on "::"
     //       to get the name of the component.
+    //
+    // `persisted_components` drops `PhantomPersistTag`: it's never written as a column (see
+    // `serialize_world`'s cluster filter), so including it here would give the schema one more
+    // field than any `RecordBatch` built from this same cluster ever has.
+    for (name, component_id) in persisted_components(components) {
+        if let Some(derived_fields) =
+            derived_component_fields(world, entities, component_id, type_registry, record_writers)
+        {
+            fields.extend(derived_fields);
+            continue;
+        }
+
+        let data_type = representative_instance(world, entities, component_id, type_registry)
+            .map(|reflect| arrow_type_for_reflect(reflect.as_ref()))
+            .unwrap_or(DataType::Utf8);
 
-    for (name, type_id) in components {
-        fields.push(Field::new(name.split("::").last().unwrap(), DataType::Utf8, true));
+        fields.push(Field::new(
+            name.split("::").last().unwrap(),
+            data_type,
+            true,
+        ));
     }
 
     Schema::new(fields)
 }
 
-/// Creates UUID array for entities
+/// If `component_id`'s type was registered in `record_writers` (via `#[derive(ParquetComponent)]`
+/// + `ParquetRecordWriterRegistry::register`), builds its explicit column list instead of the
+/// single reflection-inferred field `create_arrow_schema` would otherwise give it. Each column's
+/// type is inferred the same way as the reflection path: from one representative instance, falling
+/// back to `Utf8` if none is found.
+pub(crate) fn derived_component_fields(
+    world: &World,
+    entities: &[Entity],
+    component_id: ComponentId,
+    type_registry: &TypeRegistry,
+    record_writers: &ParquetRecordWriterRegistry,
+) -> Option<Vec<Field>> {
+    let type_id = world.components().get_info(component_id)?.type_id()?;
+    let entry = record_writers.get(type_id)?;
+    let descriptors = entry.parquet_fields();
+
+    // Reflect the live component rather than `representative_instance`'s `clone_value()`, which
+    // for a struct is a dynamic struct that `parquet_values` can't downcast to the concrete type.
+    let reflect_component = type_registry
+        .get(type_id)
+        .and_then(|registration| registration.data::<ReflectComponent>());
+    let values = reflect_component.and_then(|reflect_component| {
+        entities.iter().find_map(|&entity| {
+            world
+                .get_entity(entity)
+                .and_then(|entity_ref| reflect_component.reflect(entity_ref))
+                .and_then(|reflect| entry.parquet_values(reflect))
+        })
+    });
+
+    Some(
+        descriptors
+            .into_iter()
+            .enumerate()
+            .map(|(index, descriptor)| {
+                let data_type = values
+                    .as_ref()
+                    .and_then(|values| values.get(index))
+                    .map(|value| arrow_type_for_reflect(value.as_ref()))
+                    .unwrap_or(DataType::Utf8);
+                Field::new(&descriptor.name, data_type, descriptor.nullable)
+            })
+            .collect(),
+    )
+}
+
+/// Creates the `entity_id` join-key column shared by every cluster file from one
+/// `serialize_world` call, so a relational/star-schema consumer can reassemble a single
+/// entity's components across files with a plain Parquet/Arrow join.
+///
+/// Each value is `entity.to_bits()` formatted as a decimal string. As of this Bevy version,
+/// `to_bits()` packs the entity's index into the low 32 bits and its generation into the high 32
+/// bits, so `(index, generation)` can be reconstructed with `(bits as u32, (bits >> 32) as u32)` —
+/// but that split isn't part of Bevy's API contract, so prefer `Entity::from_bits(bits)` over
+/// hand-unpacking wherever the goal is just to get the `Entity` back, and only reach for the raw
+/// halves when you actually need the index or generation on its own (e.g. debugging, or joining
+/// against a system that only knows the index).
 pub(crate) fn create_uuid_array(entities: &[Entity]) -> ArrayRef {
     let values: Vec<Option<String>> = entities
         .iter()
@@ -1,7 +1,9 @@
+use bevy::ecs::component::{ComponentId, Tick};
 use bevy::prelude::*;
-use bevy::ecs::component::ComponentId;
 use std::collections::HashMap;
 
+use crate::writer::ParquetWriter;
+
 /// Resource holding the current serialization state
 #[derive(Default, Resource)]
 pub struct ParquetState {
@@ -9,4 +11,24 @@ pub struct ParquetState {
     pub(crate) type_to_column: HashMap<ComponentId, usize>,
     /// Detected or manually specified component clusters
     pub(crate) component_clusters: Vec<Vec<(String, ComponentId)>>,
+    /// Long-lived writers kept open across frames by the `ParquetRecorder` subsystem, keyed by
+    /// cluster signature (see `recorder::cluster_signature`) so the same cluster keeps appending
+    /// row groups to the same file instead of reopening it every snapshot.
+    pub(crate) recorder_writers: HashMap<String, ParquetWriter>,
+    /// The world change tick as of each cluster's last recorded snapshot, used to tell whether
+    /// any tracked component has changed since.
+    pub(crate) recorder_ticks: HashMap<String, Tick>,
+    /// Number of `record_tick` invocations since a cluster's last snapshot, incremented once per
+    /// call regardless of whether that call actually snapshots. `RecorderConfig::interval_frames`
+    /// counts against this, not `World::change_tick()`: the change tick is a world-wide counter
+    /// shared by every change-detection consumer and can advance by more than one per frame, so
+    /// modulo-ing it doesn't mean "every N frames".
+    pub(crate) recorder_frames_since_snapshot: HashMap<String, u32>,
+    /// Snapshots appended to a cluster's writer since its last row-group flush, used by
+    /// `RecorderConfig::flush_policy`'s `EveryFrames` variant.
+    pub(crate) recorder_snapshots_since_flush: HashMap<String, u32>,
+    /// Approximate bytes (`RecordBatch::get_array_memory_size`) appended to a cluster's writer
+    /// since its last row-group flush, used by `RecorderConfig::flush_policy`'s `RowGroupBytes`
+    /// variant.
+    pub(crate) recorder_bytes_since_flush: HashMap<String, usize>,
 }
@@ -3,7 +3,7 @@ use std::sync::Arc;
 use arrow::datatypes::SchemaRef;
 use arrow::array::RecordBatch;
 use parquet::arrow::ArrowWriter;
-use parquet::format::FileMetaData;
+use parquet::format::{FileMetaData, KeyValue};
 use parquet::file::properties::WriterProperties;
 use crate::ParquetError;
 
@@ -19,19 +19,36 @@ impl ParquetWriter {
     ) -> Result<Self, ParquetError> {
         let file = File::create(path)
             .map_err(ParquetError::Io)?;
-            
+
         let writer = ArrowWriter::try_new(file, schema, Some(properties))
             .map_err(|e| ParquetError::ParquetWrite(e.to_string()))?;
 
         Ok(Self { writer })
     }
 
+    /// Stamps `(key, value)` pairs into the file's footer (`FileMetaData::key_value_metadata`),
+    /// readable by downstream consumers without a side-channel. Must be called before
+    /// `finalize`; can be called any number of times.
+    pub fn append_key_value_metadata(&mut self, key: String, value: String) {
+        self.writer
+            .append_key_value_metadata(KeyValue::new(key, Some(value)));
+    }
+
     pub fn write_batch(&mut self, batch: &RecordBatch) -> Result<(), ParquetError> {
         self.writer
             .write(batch)
             .map_err(|e| ParquetError::ParquetWrite(e.to_string()))
     }
 
+    /// Forces everything buffered so far into a closed row group without closing the file, so a
+    /// long-running `ParquetRecorder` produces well-sized row groups instead of one giant buffer
+    /// that only flushes at `finalize`. See `RecorderConfig::flush_policy`.
+    pub fn flush(&mut self) -> Result<(), ParquetError> {
+        self.writer
+            .flush()
+            .map_err(|e| ParquetError::ParquetWrite(e.to_string()))
+    }
+
     pub fn finalize(self) -> Result<FileMetaData, ParquetError> {
         self.writer
             .close()